@@ -0,0 +1,151 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+use tauri::Emitter;
+
+/// Backoff parameters for readiness polling.
+const INITIAL_BACKOFF: Duration = Duration::from_millis(100);
+const MAX_BACKOFF: Duration = Duration::from_secs(2);
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ServiceState {
+    Starting,
+    Ready,
+    Failed,
+}
+
+/// A service that needs to report healthy before the backend is considered ready.
+#[derive(Debug, Clone)]
+pub struct ServiceHealthCheck {
+    pub name: String,
+    pub url: String,
+}
+
+impl ServiceHealthCheck {
+    pub fn new(name: impl Into<String>, url: impl Into<String>) -> Self {
+        Self { name: name.into(), url: url.into() }
+    }
+}
+
+/// Shared, aggregated view of every service's last-known health state.
+pub type HealthRegistry = Arc<Mutex<HashMap<String, ServiceState>>>;
+
+pub fn new_registry() -> HealthRegistry {
+    Arc::new(Mutex::new(HashMap::new()))
+}
+
+#[derive(Serialize, Clone)]
+struct ServiceStatusEvent<'a> {
+    name: &'a str,
+    state: ServiceState,
+    attempt: u32,
+}
+
+fn set_state(registry: &HealthRegistry, name: &str, state: ServiceState) {
+    if let Ok(mut states) = registry.lock() {
+        states.insert(name.to_string(), state);
+    }
+}
+
+/// Polls `check`'s health endpoint with exponential backoff until it responds
+/// successfully or the timeout elapses. Emits a `service-status` event on
+/// every attempt so the frontend can show live progress; emit failures are
+/// ignored and do not stop the polling.
+/// Returns `true` if the service became ready within `timeout`.
+pub fn wait_for_ready(
+    app_handle: &tauri::AppHandle,
+    registry: &HealthRegistry,
+    check: &ServiceHealthCheck,
+    timeout: Duration,
+) -> bool {
+    set_state(registry, &check.name, ServiceState::Starting);
+
+    let deadline = Instant::now() + timeout;
+    let mut backoff = INITIAL_BACKOFF;
+    let mut attempt: u32 = 0;
+
+    loop {
+        attempt += 1;
+        let _ = app_handle.emit(
+            "service-status",
+            ServiceStatusEvent { name: &check.name, state: ServiceState::Starting, attempt },
+        );
+
+        if ping(&check.url) {
+            set_state(registry, &check.name, ServiceState::Ready);
+            let _ = app_handle.emit(
+                "service-status",
+                ServiceStatusEvent { name: &check.name, state: ServiceState::Ready, attempt },
+            );
+            return true;
+        }
+
+        if Instant::now() >= deadline {
+            set_state(registry, &check.name, ServiceState::Failed);
+            let _ = app_handle.emit(
+                "service-status",
+                ServiceStatusEvent { name: &check.name, state: ServiceState::Failed, attempt },
+            );
+            return false;
+        }
+
+        thread::sleep(backoff.min(deadline.saturating_duration_since(Instant::now())));
+        backoff = (backoff * 2).min(MAX_BACKOFF);
+    }
+}
+
+fn ping(url: &str) -> bool {
+    match reqwest::blocking::Client::new()
+        .get(url)
+        .timeout(Duration::from_secs(1))
+        .send()
+    {
+        Ok(response) => response.status().is_success(),
+        Err(_) => false,
+    }
+}
+
+/// Blocks until every check is ready (or failed), emitting per-service
+/// transitions along the way. Returns `true` only if all services reported ready.
+pub fn wait_for_all_ready(
+    app_handle: &tauri::AppHandle,
+    registry: &HealthRegistry,
+    checks: &[ServiceHealthCheck],
+    total_timeout: Duration,
+) -> bool {
+    let deadline = Instant::now() + total_timeout;
+
+    let handles: Vec<_> = checks
+        .iter()
+        .cloned()
+        .map(|check| {
+            let app_handle = app_handle.clone();
+            let registry = Arc::clone(registry);
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            thread::spawn(move || wait_for_ready(&app_handle, &registry, &check, remaining))
+        })
+        .collect();
+
+    // Join every handle before judging the result — `.all()` directly on the
+    // handles would short-circuit on the first `false` and drop the rest,
+    // leaving their threads to keep polling and emitting `service-status` in
+    // the background after this function (and its caller) have moved on.
+    let results: Vec<bool> = handles.into_iter().map(|handle| handle.join().unwrap_or(false)).collect();
+    results.into_iter().all(|ready| ready)
+}
+
+/// Renders the current aggregated health state as a JSON string for the
+/// `check_service_health` command.
+pub fn snapshot(registry: &HealthRegistry) -> String {
+    let states = registry.lock().map(|states| states.clone()).unwrap_or_default();
+    serde_json::to_string(&states).unwrap_or_else(|_| "{}".to_string())
+}
+
+pub fn default_timeout() -> Duration {
+    DEFAULT_TIMEOUT
+}
@@ -1,105 +1,193 @@
+mod health;
+mod logs;
+mod proxy;
+mod services;
+mod supervisor;
+
 use std::process::{Command, Child, Stdio};
 use std::sync::{Arc, Mutex};
 use std::thread;
 use std::time::Duration;
 use tauri::{Manager, State, Emitter};
 
+use health::{HealthRegistry, ServiceHealthCheck};
+use logs::{LogRegistry, LogStream};
+use services::ServiceSpec;
+use supervisor::{ProcessTable, Supervised};
+
+fn project_root() -> Result<std::path::PathBuf, Box<dyn std::error::Error>> {
+    Ok(std::env::current_dir()?.parent().unwrap_or(std::path::Path::new(".")).to_path_buf())
+}
+
 // Backend service manager
-#[derive(Default)]
 pub struct BackendManager {
-    processes: Arc<Mutex<Vec<Child>>>,
+    processes: ProcessTable,
+    health: HealthRegistry,
+    logs: LogRegistry,
+    /// Health-check and shutdown-grace timeouts, overridden from the
+    /// manifest's `[settings]` table once `start_services` loads it. Start
+    /// out at the built-in defaults so a restart requested before the
+    /// manifest loads still behaves sanely.
+    health_timeout: Arc<Mutex<Duration>>,
+    shutdown_grace: Arc<Mutex<Duration>>,
+}
+
+impl Default for BackendManager {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl BackendManager {
     pub fn new() -> Self {
         Self {
-            processes: Arc::new(Mutex::new(Vec::new())),
+            processes: supervisor::new_table(),
+            health: health::new_registry(),
+            logs: logs::new_registry(),
+            health_timeout: Arc::new(Mutex::new(health::default_timeout())),
+            shutdown_grace: Arc::new(Mutex::new(supervisor::DEFAULT_GRACE_PERIOD)),
         }
     }
 
+    /// Returns recent log history for `service`, oldest first.
+    pub fn service_logs(&self, service: &str) -> Vec<logs::LogLine> {
+        logs::history(&self.logs, service)
+    }
+
+    /// Manually restarts `name`, bypassing backoff. Used when a service has
+    /// exhausted its automatic crash budget or an operator wants it recycled.
+    pub fn restart_service(&self, name: &str, app_handle: tauri::AppHandle) -> Result<(), String> {
+        let log_registry = Arc::clone(&self.logs);
+        let health = Arc::clone(&self.health);
+        let respawn = make_respawn(app_handle.clone(), log_registry);
+        let health_timeout = *self.health_timeout.lock().unwrap_or_else(|e| e.into_inner());
+
+        supervisor::restart_now(app_handle.clone(), &self.processes, respawn, name)?;
+
+        if let Ok(table) = self.processes.lock() {
+            if let Some(url) = table.get(name).and_then(|s| s.spec.health.clone()) {
+                let check = ServiceHealthCheck::new(name, url);
+                thread::spawn(move || {
+                    health::wait_for_ready(&app_handle, &health, &check, health_timeout);
+                });
+            }
+        }
+
+        Ok(())
+    }
+
     pub fn start_services(&self, app_handle: tauri::AppHandle) -> Result<(), Box<dyn std::error::Error>> {
         let processes = Arc::clone(&self.processes);
-        
+        let health = Arc::clone(&self.health);
+        let log_registry = Arc::clone(&self.logs);
+        let health_timeout = Arc::clone(&self.health_timeout);
+        let shutdown_grace = Arc::clone(&self.shutdown_grace);
+
         thread::spawn(move || {
-            // Start Python services first
-            if let Err(e) = start_python_services(&processes) {
-                eprintln!("Failed to start Python services: {}", e);
+            let root = match project_root() {
+                Ok(root) => root,
+                Err(e) => {
+                    eprintln!("Failed to resolve project root: {}", e);
+                    return;
+                }
+            };
+
+            let (specs, settings) = match services::load(&root) {
+                Ok(loaded) => loaded,
+                Err(e) => {
+                    eprintln!("Failed to load services.toml: {}", e);
+                    return;
+                }
+            };
+
+            let timeout = Duration::from_secs(settings.health_timeout_secs);
+            if let Ok(mut guard) = health_timeout.lock() {
+                *guard = timeout;
             }
-            
-            // Wait a bit for Python services to initialize
-            thread::sleep(Duration::from_secs(3));
-            
-            // Start Perl API server
-            if let Err(e) = start_perl_service(&processes) {
-                eprintln!("Failed to start Perl service: {}", e);
+            if let Ok(mut guard) = shutdown_grace.lock() {
+                *guard = Duration::from_secs(settings.shutdown_grace_secs);
+            }
+
+            let respawn = make_respawn(app_handle.clone(), Arc::clone(&log_registry));
+
+            let mut checks = Vec::new();
+            for spec in &specs {
+                match spawn_child(spec, &root, &app_handle, &log_registry) {
+                    Ok(child) => {
+                        if let Ok(mut table) = processes.lock() {
+                            table.insert(spec.name.clone(), Supervised::new(spec.clone(), child));
+                        }
+                        if let Some(url) = &spec.health {
+                            checks.push(ServiceHealthCheck::new(spec.name.clone(), url.clone()));
+                        }
+                    }
+                    Err(e) => eprintln!("Failed to start service '{}': {}", spec.name, e),
+                }
+            }
+
+            supervisor::spawn_monitor(app_handle.clone(), Arc::clone(&processes), respawn);
+
+            let all_ready = health::wait_for_all_ready(&app_handle, &health, &checks, timeout);
+
+            if all_ready {
+                let _ = app_handle.emit_to("main", "backend-ready", ());
+            } else {
+                eprintln!("Backend services did not become ready within the health check timeout");
             }
-            
-            // Emit ready event to frontend
-            thread::sleep(Duration::from_secs(2));
-            let _ = app_handle.emit_to("main", "backend-ready", ());
         });
-        
+
         Ok(())
     }
 
     pub fn stop_services(&self) {
-        if let Ok(mut processes) = self.processes.lock() {
-            for mut process in processes.drain(..) {
-                let _ = process.kill();
-                let _ = process.wait();
-            }
-        }
+        let grace_period = *self.shutdown_grace.lock().unwrap_or_else(|e| e.into_inner());
+        supervisor::stop_all_graceful(&self.processes, grace_period);
     }
 }
 
-fn start_python_services(processes: &Arc<Mutex<Vec<Child>>>) -> Result<(), Box<dyn std::error::Error>> {
-    // Get the project root directory (parent of tauri)
-    let project_root = std::env::current_dir()?.parent().unwrap_or(std::path::Path::new(".")).to_path_buf();
-    let backend_path = project_root.join("backend").join("python-backend");
-    
-    // Start embedding service
-    let embedding_service = Command::new("./venv/bin/python3")
-        .arg("-m")
-        .arg("src.services.embedding_service")
-        .current_dir(&backend_path)
-        .stdout(Stdio::piped())
-        .stderr(Stdio::piped())
-        .spawn()?;
-    
-    // Start Gemini service
-    let gemini_service = Command::new("./venv/bin/python3")
-        .arg("-m")
-        .arg("src.services.gemini_service")
-        .current_dir(&backend_path)
-        .stdout(Stdio::piped())
-        .stderr(Stdio::piped())
-        .spawn()?;
-    
-    if let Ok(mut procs) = processes.lock() {
-        procs.push(embedding_service);
-        procs.push(gemini_service);
+/// Builds the closure the supervisor uses to relaunch a crashed or manually
+/// restarted service, re-wiring its logs the same way the initial spawn did.
+fn make_respawn(
+    app_handle: tauri::AppHandle,
+    log_registry: LogRegistry,
+) -> impl Fn(&ServiceSpec) -> Result<Child, Box<dyn std::error::Error + Send + Sync>> {
+    move |spec: &ServiceSpec| {
+        let root = project_root().map_err(|e| -> Box<dyn std::error::Error + Send + Sync> { e.to_string().into() })?;
+        spawn_child(spec, &root, &app_handle, &log_registry)
+            .map_err(|e| -> Box<dyn std::error::Error + Send + Sync> { e.to_string().into() })
     }
-    
-    Ok(())
 }
 
-fn start_perl_service(processes: &Arc<Mutex<Vec<Child>>>) -> Result<(), Box<dyn std::error::Error>> {
-    // Get the project root directory (parent of tauri)
-    let project_root = std::env::current_dir()?.parent().unwrap_or(std::path::Path::new(".")).to_path_buf();
-    let backend_path = project_root.join("backend");
-    
-    let perl_service = Command::new("perl")
-        .arg("perl-backend/script/api_server.pl")
-        .current_dir(&backend_path)
+/// Spawns a single manifest-declared service and wires its stdout/stderr into
+/// the log-forwarding subsystem. Does not track the child for supervision —
+/// callers are responsible for recording it in the process table.
+fn spawn_child(
+    spec: &ServiceSpec,
+    project_root: &std::path::Path,
+    app_handle: &tauri::AppHandle,
+    log_registry: &LogRegistry,
+) -> Result<Child, Box<dyn std::error::Error + Send + Sync>> {
+    let cwd = match &spec.cwd {
+        Some(cwd) => project_root.join(cwd),
+        None => project_root.to_path_buf(),
+    };
+
+    let mut child = Command::new(&spec.command)
+        .args(&spec.args)
+        .current_dir(&cwd)
+        .envs(&spec.env)
         .stdout(Stdio::piped())
         .stderr(Stdio::piped())
         .spawn()?;
-    
-    if let Ok(mut procs) = processes.lock() {
-        procs.push(perl_service);
+
+    if let Some(stdout) = child.stdout.take() {
+        logs::forward(app_handle.clone(), Arc::clone(log_registry), spec.name.clone(), LogStream::Stdout, stdout);
+    }
+    if let Some(stderr) = child.stderr.take() {
+        logs::forward(app_handle.clone(), Arc::clone(log_registry), spec.name.clone(), LogStream::Stderr, stderr);
     }
-    
-    Ok(())
+
+    Ok(child)
 }
 
 // Tauri commands
@@ -121,21 +209,60 @@ async fn stop_backend_services(backend_manager: State<'_, BackendManager>) -> Re
 }
 
 #[tauri::command]
-async fn check_service_health() -> Result<String, String> {
-    // Simple health check - could be expanded
-    Ok("Services running".to_string())
+async fn check_service_health(backend_manager: State<'_, BackendManager>) -> Result<String, String> {
+    Ok(health::snapshot(&backend_manager.health))
+}
+
+#[tauri::command]
+async fn get_service_logs(
+    backend_manager: State<'_, BackendManager>,
+    service: String,
+) -> Result<Vec<logs::LogLine>, String> {
+    Ok(backend_manager.service_logs(&service))
+}
+
+#[tauri::command]
+async fn restart_service(
+    backend_manager: State<'_, BackendManager>,
+    app_handle: tauri::AppHandle,
+    name: String,
+) -> Result<String, String> {
+    backend_manager.restart_service(&name, app_handle)?;
+    Ok(format!("Restarting {}", name))
 }
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
     let backend_manager = BackendManager::new();
-    
+
+    // Derive proxy targets from the service manifest so the frontend's view
+    // of "where is the embedding service" stays in one place; fall back to
+    // the conventional ports if the manifest can't be read yet.
+    let proxy_targets = project_root()
+        .ok()
+        .and_then(|root| services::load(&root).ok())
+        .map(|(specs, _settings)| proxy::targets_from_specs(&specs))
+        .unwrap_or_default();
+    let api_proxy = Arc::new(proxy::ApiProxy::new(proxy_targets));
+
     tauri::Builder::default()
         .manage(backend_manager)
+        .manage(api_proxy)
+        .register_asynchronous_uri_scheme_protocol("tessera", |ctx, request, responder| {
+            let app_handle = ctx.app_handle().clone();
+            tauri::async_runtime::spawn(async move {
+                let api_proxy = app_handle.state::<Arc<proxy::ApiProxy>>();
+                let axum_request = proxy::to_axum_request(request);
+                let response = api_proxy.dispatch(axum_request).await;
+                responder.respond(response);
+            });
+        })
         .invoke_handler(tauri::generate_handler![
             start_backend_services,
             stop_backend_services,
-            check_service_health
+            check_service_health,
+            get_service_logs,
+            restart_service
         ])
         .setup(|app| {
             if cfg!(debug_assertions) {
@@ -156,10 +283,20 @@ pub fn run() {
             Ok(())
         })
         .on_window_event(|window, event| {
-            if let tauri::WindowEvent::CloseRequested { .. } = event {
-                // Stop backend services when window closes
-                let backend_manager = window.state::<BackendManager>();
-                backend_manager.stop_services();
+            if let tauri::WindowEvent::CloseRequested { api, .. } = event {
+                // Draining services (shutdown HTTP calls + SIGTERM + grace
+                // period) can take several seconds; do it off the event-loop
+                // thread so the window doesn't appear to hang, then close
+                // once everything is actually down.
+                api.prevent_close();
+                let window = window.clone();
+                thread::spawn(move || {
+                    let backend_manager = window.state::<BackendManager>();
+                    backend_manager.stop_services();
+                    // `close()` would raise CloseRequested again and be
+                    // prevented by this same handler; `destroy()` does not.
+                    let _ = window.destroy();
+                });
             }
         })
         .run(tauri::generate_context!())
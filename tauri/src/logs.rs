@@ -0,0 +1,86 @@
+use std::collections::{HashMap, VecDeque};
+use std::io::{BufRead, BufReader, Read};
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+use serde::Serialize;
+use tauri::Emitter;
+
+/// How many lines of history we keep per service for late-joining windows.
+const LINES_PER_SERVICE: usize = 1000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LogStream {
+    Stdout,
+    Stderr,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct LogLine {
+    pub service: String,
+    pub stream: LogStream,
+    pub line: String,
+    pub ts: u64,
+}
+
+/// Ring buffers of recent log lines, keyed by service name.
+pub type LogRegistry = Arc<Mutex<HashMap<String, VecDeque<LogLine>>>>;
+
+pub fn new_registry() -> LogRegistry {
+    Arc::new(Mutex::new(HashMap::new()))
+}
+
+fn record(registry: &LogRegistry, line: LogLine) {
+    if let Ok(mut services) = registry.lock() {
+        let buffer = services.entry(line.service.clone()).or_default();
+        if buffer.len() >= LINES_PER_SERVICE {
+            buffer.pop_front();
+        }
+        buffer.push_back(line);
+    }
+}
+
+/// Spawns a thread that reads `pipe` line by line, forwarding each line to the
+/// frontend as a `service-log` event and appending it to the bounded history
+/// buffer for `service`.
+pub fn forward<R: Read + Send + 'static>(
+    app_handle: tauri::AppHandle,
+    registry: LogRegistry,
+    service: impl Into<String>,
+    stream: LogStream,
+    pipe: R,
+) {
+    let service = service.into();
+    thread::spawn(move || {
+        let reader = BufReader::new(pipe);
+        for line in reader.lines() {
+            let Ok(line) = line else { break };
+            let entry = LogLine {
+                service: service.clone(),
+                stream,
+                line,
+                ts: now_millis(),
+            };
+            record(&registry, entry.clone());
+            let _ = app_handle.emit("service-log", &entry);
+        }
+    });
+}
+
+fn now_millis() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+/// Returns the buffered history for `service`, oldest first.
+pub fn history(registry: &LogRegistry, service: &str) -> Vec<LogLine> {
+    registry
+        .lock()
+        .ok()
+        .and_then(|services| services.get(service).cloned())
+        .map(|buffer| buffer.into_iter().collect())
+        .unwrap_or_default()
+}
@@ -0,0 +1,156 @@
+use axum::body::Body;
+use axum::http::header::{HeaderName, CONNECTION, CONTENT_LENGTH, TRANSFER_ENCODING};
+use axum::routing::any;
+use axum::Router;
+use http_body_util::BodyExt;
+use tower::ServiceExt;
+
+use crate::services::ServiceSpec;
+
+/// Headers that describe a specific hop's framing (connection handling,
+/// chunking, body length) rather than the resource itself. Both legs of this
+/// proxy fully re-buffer the body, so carrying these across verbatim would
+/// describe framing that no longer matches what's actually sent.
+const HOP_BY_HOP_HEADERS: &[HeaderName] = &[CONNECTION, CONTENT_LENGTH, TRANSFER_ENCODING];
+
+fn is_hop_by_hop(name: &HeaderName) -> bool {
+    HOP_BY_HOP_HEADERS.contains(name)
+}
+
+/// Base URLs for each backend the proxy forwards requests to, keyed by the
+/// `tessera://api/<prefix>/...` path prefix.
+#[derive(Clone)]
+pub struct ProxyTargets {
+    pub embedding: String,
+    pub gemini: String,
+    pub perl: String,
+}
+
+impl Default for ProxyTargets {
+    fn default() -> Self {
+        Self {
+            embedding: "http://127.0.0.1:8001".to_string(),
+            gemini: "http://127.0.0.1:8002".to_string(),
+            perl: "http://127.0.0.1:5000".to_string(),
+        }
+    }
+}
+
+/// Derives proxy targets from the loaded service manifest by stripping the
+/// `/health` suffix off each service's health endpoint, so the frontend's
+/// view of "where is the embedding service" stays in one place.
+pub fn targets_from_specs(specs: &[ServiceSpec]) -> ProxyTargets {
+    let base_of = |name: &str| -> Option<String> {
+        specs
+            .iter()
+            .find(|spec| spec.name == name)
+            .and_then(|spec| spec.health.as_deref())
+            .map(|health| health.trim_end_matches("/health").to_string())
+    };
+
+    let defaults = ProxyTargets::default();
+    ProxyTargets {
+        embedding: base_of("embedding-service").unwrap_or(defaults.embedding),
+        gemini: base_of("gemini-service").unwrap_or(defaults.gemini),
+        perl: base_of("perl-api").unwrap_or(defaults.perl),
+    }
+}
+
+/// The in-process axum router backing the `tessera://api` custom protocol.
+/// `Router` is cheaply `Clone` (it's `Arc`-backed internally), so each
+/// dispatch clones and drives its own copy rather than serializing every
+/// request through a shared lock.
+pub struct ApiProxy {
+    router: Router,
+}
+
+impl ApiProxy {
+    pub fn new(targets: ProxyTargets) -> Self {
+        Self { router: build_router(targets) }
+    }
+
+    /// Routes a converted Tauri request through the proxy router and reads
+    /// the backend's full response body to bytes before handing it back.
+    pub async fn dispatch(&self, request: axum::http::Request<Body>) -> tauri::http::Response<Vec<u8>> {
+        let response = match self.router.clone().oneshot(request).await {
+            Ok(response) => response,
+            Err(infallible) => match infallible {},
+        };
+
+        to_tauri_response(response).await
+    }
+}
+
+fn build_router(targets: ProxyTargets) -> Router {
+    Router::new()
+        .route("/embedding/*path", any(move |req| proxy_to(targets.embedding.clone(), req)))
+        .route("/gemini/*path", any(move |req| proxy_to(targets.gemini.clone(), req)))
+        .route("/perl/*path", any(move |req| proxy_to(targets.perl.clone(), req)))
+}
+
+async fn proxy_to(base: String, req: axum::http::Request<Body>) -> axum::http::Response<Body> {
+    let (parts, body) = req.into_parts();
+    let path_and_query = parts.uri.path_and_query().map(|p| p.as_str()).unwrap_or("/");
+    let url = format!("{}{}", base.trim_end_matches('/'), path_and_query);
+
+    let body_bytes = body
+        .collect()
+        .await
+        .map(|collected| collected.to_bytes())
+        .unwrap_or_default();
+
+    let client = reqwest::Client::new();
+    let mut upstream_request = client.request(parts.method.clone(), &url).body(body_bytes.to_vec());
+    for (name, value) in parts.headers.iter().filter(|(name, _)| !is_hop_by_hop(name)) {
+        upstream_request = upstream_request.header(name, value);
+    }
+
+    match upstream_request.send().await {
+        Ok(upstream) => {
+            let status = upstream.status();
+            let headers = upstream.headers().clone();
+            let bytes = upstream.bytes().await.unwrap_or_default();
+
+            let mut response = axum::http::Response::builder().status(status);
+            for (name, value) in headers.iter().filter(|(name, _)| !is_hop_by_hop(name)) {
+                response = response.header(name, value);
+            }
+            response
+                .body(Body::from(bytes))
+                .unwrap_or_else(|_| bad_gateway())
+        }
+        Err(_) => bad_gateway(),
+    }
+}
+
+fn bad_gateway() -> axum::http::Response<Body> {
+    axum::http::Response::builder()
+        .status(axum::http::StatusCode::BAD_GATEWAY)
+        .body(Body::from("upstream request failed"))
+        .expect("static response is well-formed")
+}
+
+/// Reads the axum response body to bytes and rebuilds it as the plain,
+/// fully-buffered response type Tauri's custom protocol handler expects.
+async fn to_tauri_response(response: axum::http::Response<Body>) -> tauri::http::Response<Vec<u8>> {
+    let (parts, body) = response.into_parts();
+    let bytes = body.collect().await.map(|collected| collected.to_bytes()).unwrap_or_default();
+
+    let mut builder = tauri::http::Response::builder().status(parts.status);
+    for (name, value) in parts.headers.iter().filter(|(name, _)| !is_hop_by_hop(name)) {
+        builder = builder.header(name, value);
+    }
+    builder.body(bytes.to_vec()).unwrap_or_else(|_| {
+        tauri::http::Response::builder()
+            .status(502)
+            .body(Vec::new())
+            .expect("static response is well-formed")
+    })
+}
+
+/// Converts an incoming Tauri custom-protocol request into the axum request
+/// type the in-process router expects.
+pub fn to_axum_request(request: tauri::http::Request<Vec<u8>>) -> axum::http::Request<Body> {
+    let (parts, body) = request.into_parts();
+    axum::http::Request::from_parts(parts, Body::from(body))
+}
@@ -0,0 +1,209 @@
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+
+use serde::Deserialize;
+
+/// One entry in `services.toml`: everything needed to spawn and supervise a
+/// single backend process.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ServiceSpec {
+    pub name: String,
+    pub command: String,
+    #[serde(default)]
+    pub args: Vec<String>,
+    #[serde(default)]
+    pub cwd: Option<PathBuf>,
+    #[serde(default)]
+    pub env: HashMap<String, String>,
+    #[serde(default)]
+    pub depends_on: Vec<String>,
+    pub health: Option<String>,
+    /// Optional endpoint to call before sending a termination signal, so the
+    /// service can checkpoint state and shut down cleanly.
+    #[serde(default)]
+    pub shutdown: Option<String>,
+}
+
+/// Tunables that apply across the whole backend, set via an optional
+/// `[settings]` table in `services.toml` rather than hardcoded constants.
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    pub health_timeout_secs: u64,
+    pub shutdown_grace_secs: u64,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self { health_timeout_secs: 30, shutdown_grace_secs: 5 }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct Manifest {
+    #[serde(rename = "service")]
+    services: Vec<ServiceSpec>,
+    #[serde(default)]
+    settings: Settings,
+}
+
+/// Loads `services.toml` from the project root (the directory above `tauri/`).
+pub fn load(project_root: &std::path::Path) -> Result<(Vec<ServiceSpec>, Settings), Box<dyn std::error::Error>> {
+    let manifest_path = project_root.join("services.toml");
+    let contents = std::fs::read_to_string(&manifest_path)
+        .map_err(|e| format!("failed to read {}: {}", manifest_path.display(), e))?;
+    let mut manifest: Manifest = toml::from_str(&contents)?;
+
+    for service in &mut manifest.services {
+        for value in service.env.values_mut() {
+            *value = expand_env_vars(value);
+        }
+    }
+
+    let services = order_by_dependencies(manifest.services)?;
+    Ok((services, manifest.settings))
+}
+
+/// Substitutes `${VAR}` placeholders in `value` with the value of `VAR` from
+/// the process environment, so secrets like API keys can be referenced from
+/// `services.toml` instead of hardcoded. A placeholder whose variable isn't
+/// set is left untouched.
+fn expand_env_vars(value: &str) -> String {
+    let mut result = String::with_capacity(value.len());
+    let mut rest = value;
+
+    while let Some(start) = rest.find("${") {
+        let Some(end) = rest[start..].find('}') else {
+            result.push_str(rest);
+            return result;
+        };
+        let end = start + end;
+
+        result.push_str(&rest[..start]);
+        let var_name = &rest[start + 2..end];
+        match std::env::var(var_name) {
+            Ok(resolved) => result.push_str(&resolved),
+            Err(_) => result.push_str(&rest[start..=end]),
+        }
+        rest = &rest[end + 1..];
+    }
+
+    result.push_str(rest);
+    result
+}
+
+/// Topologically sorts services so that every entry appears after everything
+/// it `depends_on`. Errors on an unknown dependency or a cycle.
+fn order_by_dependencies(services: Vec<ServiceSpec>) -> Result<Vec<ServiceSpec>, Box<dyn std::error::Error>> {
+    let by_name: HashMap<&str, &ServiceSpec> =
+        services.iter().map(|s| (s.name.as_str(), s)).collect();
+
+    for service in &services {
+        for dep in &service.depends_on {
+            if !by_name.contains_key(dep.as_str()) {
+                return Err(format!(
+                    "service '{}' depends on unknown service '{}'",
+                    service.name, dep
+                )
+                .into());
+            }
+        }
+    }
+
+    let mut ordered = Vec::with_capacity(services.len());
+    let mut visited: HashSet<&str> = HashSet::new();
+    let mut visiting: HashSet<&str> = HashSet::new();
+
+    fn visit<'a>(
+        name: &'a str,
+        by_name: &HashMap<&'a str, &'a ServiceSpec>,
+        visited: &mut HashSet<&'a str>,
+        visiting: &mut HashSet<&'a str>,
+        ordered: &mut Vec<ServiceSpec>,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        if visited.contains(name) {
+            return Ok(());
+        }
+        if !visiting.insert(name) {
+            return Err(format!("dependency cycle detected at service '{}'", name).into());
+        }
+
+        let service = by_name[name];
+        for dep in &service.depends_on {
+            visit(dep.as_str(), by_name, visited, visiting, ordered)?;
+        }
+
+        visiting.remove(name);
+        visited.insert(name);
+        ordered.push((*service).clone());
+        Ok(())
+    }
+
+    for service in &services {
+        visit(service.name.as_str(), &by_name, &mut visited, &mut visiting, &mut ordered)?;
+    }
+
+    Ok(ordered)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn spec(name: &str, depends_on: &[&str]) -> ServiceSpec {
+        ServiceSpec {
+            name: name.to_string(),
+            command: "true".to_string(),
+            args: Vec::new(),
+            cwd: None,
+            env: HashMap::new(),
+            depends_on: depends_on.iter().map(|d| d.to_string()).collect(),
+            health: None,
+            shutdown: None,
+        }
+    }
+
+    #[test]
+    fn orders_dependencies_before_dependents() {
+        let services = vec![
+            spec("perl-api", &["embedding-service", "gemini-service"]),
+            spec("embedding-service", &[]),
+            spec("gemini-service", &[]),
+        ];
+
+        let ordered = order_by_dependencies(services).unwrap();
+        let position = |name: &str| ordered.iter().position(|s| s.name == name).unwrap();
+
+        assert!(position("embedding-service") < position("perl-api"));
+        assert!(position("gemini-service") < position("perl-api"));
+    }
+
+    #[test]
+    fn rejects_unknown_dependency() {
+        let services = vec![spec("perl-api", &["does-not-exist"])];
+
+        let err = order_by_dependencies(services).unwrap_err();
+        assert!(err.to_string().contains("unknown service"));
+    }
+
+    #[test]
+    fn rejects_dependency_cycle() {
+        let services = vec![spec("a", &["b"]), spec("b", &["a"])];
+
+        let err = order_by_dependencies(services).unwrap_err();
+        assert!(err.to_string().contains("cycle"));
+    }
+
+    #[test]
+    fn expands_known_env_var() {
+        std::env::set_var("TESSERA_TEST_VAR", "resolved");
+        assert_eq!(expand_env_vars("${TESSERA_TEST_VAR}"), "resolved");
+        std::env::remove_var("TESSERA_TEST_VAR");
+    }
+
+    #[test]
+    fn leaves_unset_placeholder_untouched() {
+        std::env::remove_var("TESSERA_DEFINITELY_UNSET");
+        assert_eq!(expand_env_vars("${TESSERA_DEFINITELY_UNSET}"), "${TESSERA_DEFINITELY_UNSET}");
+    }
+}
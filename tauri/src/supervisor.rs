@@ -0,0 +1,342 @@
+use std::collections::{HashMap, VecDeque};
+use std::process::Child;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
+use tauri::Emitter;
+
+use crate::services::ServiceSpec;
+
+/// How often the monitor thread checks on supervised children.
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+/// Crash-loop window: at most `MAX_CRASHES_IN_WINDOW` restarts are attempted
+/// within this rolling window before a service is left down for manual recovery.
+const CRASH_WINDOW: Duration = Duration::from_secs(60);
+const MAX_CRASHES_IN_WINDOW: usize = 5;
+const MAX_BACKOFF: Duration = Duration::from_secs(30);
+/// A service that has stayed up this long has its crash history forgiven.
+const STABILITY_THRESHOLD: Duration = Duration::from_secs(60);
+/// Default grace period `stop_all_graceful` waits for SIGTERM to take effect
+/// before force-killing whatever is still alive.
+pub const DEFAULT_GRACE_PERIOD: Duration = Duration::from_secs(5);
+/// How often we poll `try_wait` while draining during a graceful shutdown.
+const DRAIN_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// A running (or temporarily down) service, tracked for crash detection and
+/// restart backoff. `child` is `None` while a restart is pending or the
+/// service has exhausted its crash budget and needs a manual `restart_service`.
+pub struct Supervised {
+    pub spec: ServiceSpec,
+    pub child: Option<Child>,
+    pub started_at: Instant,
+    pub restart_count: u32,
+    pub crash_times: VecDeque<Instant>,
+}
+
+impl Supervised {
+    pub fn new(spec: ServiceSpec, child: Child) -> Self {
+        Self {
+            spec,
+            child: Some(child),
+            started_at: Instant::now(),
+            restart_count: 0,
+            crash_times: VecDeque::new(),
+        }
+    }
+}
+
+/// Services currently known to the supervisor, keyed by name.
+pub type ProcessTable = Arc<Mutex<HashMap<String, Supervised>>>;
+
+pub fn new_table() -> ProcessTable {
+    Arc::new(Mutex::new(HashMap::new()))
+}
+
+#[derive(Serialize, Clone)]
+struct ServiceCrashedEvent<'a> {
+    name: &'a str,
+    restart_count: u32,
+}
+
+/// Emitted once a service's crash budget is exhausted and the supervisor has
+/// stopped retrying automatically; `restart_service` is the only way back.
+#[derive(Serialize, Clone)]
+struct ServiceFailedEvent<'a> {
+    name: &'a str,
+    restart_count: u32,
+}
+
+fn backoff_for(restart_count: u32) -> Duration {
+    if restart_count == 0 {
+        return Duration::ZERO;
+    }
+    let exponent = (restart_count - 1).min(5);
+    Duration::from_secs(1 << exponent).min(MAX_BACKOFF)
+}
+
+fn prune_crash_window(crash_times: &mut VecDeque<Instant>, now: Instant) {
+    while let Some(oldest) = crash_times.front() {
+        if now.duration_since(*oldest) > CRASH_WINDOW {
+            crash_times.pop_front();
+        } else {
+            break;
+        }
+    }
+}
+
+/// Spawns the background monitor thread. `respawn` re-launches a crashed
+/// service from its spec (wiring logs the same way the initial spawn did) and
+/// is shared with the manual `restart_service` command. No health polling is
+/// run after an automatic restart.
+pub fn spawn_monitor<F>(app_handle: tauri::AppHandle, processes: ProcessTable, respawn: F)
+where
+    F: Fn(&ServiceSpec) -> Result<Child, Box<dyn std::error::Error + Send + Sync>> + Send + Sync + 'static,
+{
+    let respawn = Arc::new(respawn);
+
+    thread::spawn(move || loop {
+        thread::sleep(POLL_INTERVAL);
+
+        let crashed: Vec<(String, u32)> = {
+            let mut table = match processes.lock() {
+                Ok(table) => table,
+                Err(_) => continue,
+            };
+
+            let mut crashed = Vec::new();
+            for (name, supervised) in table.iter_mut() {
+                if supervised.child.is_some() && supervised.started_at.elapsed() >= STABILITY_THRESHOLD {
+                    supervised.restart_count = 0;
+                    supervised.crash_times.clear();
+                }
+
+                let exited = match &mut supervised.child {
+                    Some(child) => matches!(child.try_wait(), Ok(Some(_))),
+                    None => false,
+                };
+
+                if exited {
+                    supervised.child = None;
+                    let now = Instant::now();
+                    prune_crash_window(&mut supervised.crash_times, now);
+                    supervised.crash_times.push_back(now);
+                    crashed.push((name.clone(), supervised.restart_count));
+                }
+            }
+            crashed
+        };
+
+        for (name, restart_count) in crashed {
+            let _ = app_handle.emit("service-crashed", ServiceCrashedEvent { name: &name, restart_count });
+            schedule_restart_or_give_up(
+                app_handle.clone(),
+                Arc::clone(&processes),
+                Arc::clone(&respawn),
+                name,
+                restart_count,
+            );
+        }
+    });
+}
+
+/// Checks the crash budget for `name` and either schedules a backed-off
+/// restart attempt or gives up and emits a terminal `service-failed` event.
+fn schedule_restart_or_give_up<F>(
+    app_handle: tauri::AppHandle,
+    processes: ProcessTable,
+    respawn: Arc<F>,
+    name: String,
+    restart_count: u32,
+) where
+    F: Fn(&ServiceSpec) -> Result<Child, Box<dyn std::error::Error + Send + Sync>> + Send + Sync + 'static,
+{
+    let within_budget = processes
+        .lock()
+        .ok()
+        .and_then(|table| table.get(&name).map(|s| s.crash_times.len() <= MAX_CRASHES_IN_WINDOW))
+        .unwrap_or(false);
+
+    if !within_budget {
+        eprintln!("service '{}' crashed more than {} times within {:?}; giving up, use restart_service to recover", name, MAX_CRASHES_IN_WINDOW, CRASH_WINDOW);
+        let _ = app_handle.emit("service-failed", ServiceFailedEvent { name: &name, restart_count });
+        return;
+    }
+
+    let backoff = backoff_for(restart_count);
+
+    thread::spawn(move || {
+        if !backoff.is_zero() {
+            thread::sleep(backoff);
+        }
+        restart(app_handle, processes, respawn, name, restart_count);
+    });
+}
+
+/// Attempts to respawn `name`. On success, records the new child and bumps
+/// its restart count. On failure, the attempt itself counts as another crash
+/// against the budget, and another restart is scheduled (or the service is
+/// given up on) rather than leaving it stranded with no child and no further
+/// automatic retries.
+fn restart<F>(app_handle: tauri::AppHandle, processes: ProcessTable, respawn: Arc<F>, name: String, restart_count: u32)
+where
+    F: Fn(&ServiceSpec) -> Result<Child, Box<dyn std::error::Error + Send + Sync>> + Send + Sync + 'static,
+{
+    let spec = {
+        let table = match processes.lock() {
+            Ok(table) => table,
+            Err(_) => return,
+        };
+        match table.get(&name) {
+            Some(supervised) => supervised.spec.clone(),
+            None => return,
+        }
+    };
+
+    match respawn(&spec) {
+        Ok(child) => {
+            if let Ok(mut table) = processes.lock() {
+                if let Some(supervised) = table.get_mut(&name) {
+                    supervised.child = Some(child);
+                    supervised.started_at = Instant::now();
+                    supervised.restart_count = restart_count + 1;
+                }
+            }
+        }
+        Err(e) => {
+            eprintln!("failed to restart service '{}': {}", name, e);
+
+            let now = Instant::now();
+            if let Ok(mut table) = processes.lock() {
+                if let Some(supervised) = table.get_mut(&name) {
+                    prune_crash_window(&mut supervised.crash_times, now);
+                    supervised.crash_times.push_back(now);
+                }
+            }
+
+            schedule_restart_or_give_up(app_handle, processes, respawn, name, restart_count + 1);
+        }
+    }
+}
+
+/// Manually restarts `name`, bypassing backoff and resetting its crash
+/// history. Used by the `restart_service` Tauri command.
+pub fn restart_now<F>(app_handle: tauri::AppHandle, processes: &ProcessTable, respawn: F, name: &str) -> Result<(), String>
+where
+    F: Fn(&ServiceSpec) -> Result<Child, Box<dyn std::error::Error + Send + Sync>> + Send + Sync + 'static,
+{
+    {
+        let mut table = processes.lock().map_err(|_| "process table poisoned".to_string())?;
+        match table.get_mut(name) {
+            Some(supervised) => {
+                if let Some(mut child) = supervised.child.take() {
+                    let _ = child.kill();
+                    let _ = child.wait();
+                }
+                supervised.crash_times.clear();
+            }
+            None => return Err(format!("unknown service '{}'", name)),
+        }
+    }
+
+    restart(app_handle, Arc::clone(processes), Arc::new(respawn), name.to_string(), 0);
+    Ok(())
+}
+
+/// Sends a platform-appropriate termination signal to `pid` (SIGTERM on
+/// Unix). There is no portable equivalent on Windows, where the caller falls
+/// straight through to a hard kill once the grace period elapses.
+#[cfg(unix)]
+fn terminate(pid: u32) {
+    unsafe {
+        libc::kill(pid as libc::pid_t, libc::SIGTERM);
+    }
+}
+
+#[cfg(not(unix))]
+fn terminate(_pid: u32) {}
+
+/// Calls a service's shutdown endpoint, if it declared one, so it can
+/// checkpoint state before the termination signal lands.
+fn notify_shutdown(url: &str) {
+    let _ = reqwest::blocking::Client::new()
+        .post(url)
+        .timeout(Duration::from_secs(2))
+        .send();
+}
+
+/// Two-phase shutdown: ask every service to checkpoint, send SIGTERM, wait up
+/// to `grace_period` polling `try_wait`, then force-kill whatever is still
+/// alive.
+pub fn stop_all_graceful(processes: &ProcessTable, grace_period: Duration) {
+    let mut table = match processes.lock() {
+        Ok(table) => table,
+        Err(_) => return,
+    };
+
+    for supervised in table.values() {
+        if let Some(url) = &supervised.spec.shutdown {
+            notify_shutdown(url);
+        }
+    }
+
+    for supervised in table.values() {
+        if let Some(child) = &supervised.child {
+            terminate(child.id());
+        }
+    }
+
+    let deadline = Instant::now() + grace_period;
+    loop {
+        let all_exited = table
+            .values_mut()
+            .filter_map(|supervised| supervised.child.as_mut())
+            .all(|child| matches!(child.try_wait(), Ok(Some(_))));
+
+        if all_exited || Instant::now() >= deadline {
+            break;
+        }
+        thread::sleep(DRAIN_POLL_INTERVAL);
+    }
+
+    for (_, mut supervised) in table.drain() {
+        if let Some(mut child) = supervised.child.take() {
+            let _ = child.kill();
+            let _ = child.wait();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn backoff_is_zero_for_the_first_attempt() {
+        assert_eq!(backoff_for(0), Duration::ZERO);
+    }
+
+    #[test]
+    fn backoff_doubles_and_caps_at_max() {
+        assert_eq!(backoff_for(1), Duration::from_secs(1));
+        assert_eq!(backoff_for(2), Duration::from_secs(2));
+        assert_eq!(backoff_for(3), Duration::from_secs(4));
+        assert_eq!(backoff_for(4), Duration::from_secs(8));
+        assert_eq!(backoff_for(7), MAX_BACKOFF);
+        assert_eq!(backoff_for(100), MAX_BACKOFF);
+    }
+
+    #[test]
+    fn prune_crash_window_drops_only_stale_entries() {
+        let now = Instant::now();
+        let mut crash_times: VecDeque<Instant> = VecDeque::new();
+        crash_times.push_back(now - CRASH_WINDOW - Duration::from_secs(1));
+        crash_times.push_back(now - Duration::from_secs(1));
+
+        prune_crash_window(&mut crash_times, now);
+
+        assert_eq!(crash_times.len(), 1);
+        assert_eq!(*crash_times.front().unwrap(), now - Duration::from_secs(1));
+    }
+}